@@ -5,17 +5,147 @@
 
 use super::Control;
 use callback_helpers::{from_void_ptr, to_heap_ptr};
+use std::cell::{Cell, RefCell};
 use std::ffi::{CStr, CString};
 use std::mem;
+use std::ops::Range;
+use std::os::raw::c_char;
 use std::os::raw::c_int;
 use std::os::raw::c_void;
+use std::rc::Rc;
 use str_tools::{from_toolkit_string, to_toolkit_string};
 use ui_sys::{self, uiControl, uiEntry, uiMultilineEntry};
 
-pub trait TextEntry {
+/// Converts a UTF-8 byte offset in the toolkit's native buffer (which may use
+/// `\r\n` line endings on Windows) into an offset in this crate's normalized,
+/// `\n`-only buffer, so that `selection`/`caret` stay stable across that
+/// translation layer.
+fn to_normalized_offset(native: &str, native_offset: usize) -> usize {
+    let removed = native
+        .match_indices("\r\n")
+        .filter(|&(i, _)| i < native_offset)
+        .count();
+    native_offset - removed
+}
+
+/// The inverse of `to_normalized_offset`: converts an offset into the
+/// normalized `\n`-only buffer back into a native toolkit offset.
+///
+/// Only Windows' native buffer actually uses `\r\n`; on every other platform
+/// the native and normalized buffers are identical, so this is a no-op there.
+/// Counting `\n` unconditionally would place every offset one byte too far
+/// for each preceding line on non-Windows targets.
+#[cfg(windows)]
+fn to_native_offset(normalized: &str, offset: usize) -> usize {
+    let added = normalized.match_indices('\n').filter(|&(i, _)| i < offset).count();
+    offset + added
+}
+
+#[cfg(not(windows))]
+fn to_native_offset(_normalized: &str, offset: usize) -> usize {
+    offset
+}
+
+// Shared by every `TextEntry` impl's `selection`/`set_selection`: only the
+// underlying `uiEntry`/`uiMultilineEntry` functions differ per control type.
+unsafe fn selection_from_native<T>(
+    native_text: *const c_char,
+    handle: *mut T,
+    get_bounds: unsafe extern "C" fn(*mut T, *mut usize, *mut usize),
+) -> Range<usize> {
+    let native = CStr::from_ptr(native_text).to_string_lossy().into_owned();
+    let mut start: usize = 0;
+    let mut end: usize = 0;
+    get_bounds(handle, &mut start, &mut end);
+
+    to_normalized_offset(&native, start)..to_normalized_offset(&native, end)
+}
+
+unsafe fn set_selection_on<T>(
+    value: &str,
+    handle: *mut T,
+    range: Range<usize>,
+    set_bounds: unsafe extern "C" fn(*mut T, usize, usize),
+) {
+    let start = to_native_offset(value, range.start);
+    let end = to_native_offset(value, range.end);
+    set_bounds(handle, start, end);
+}
+
+pub trait TextEntry: Sized {
     fn value(&self) -> String;
     fn set_value(&mut self, value: &str);
     fn on_changed<'ctx, F: FnMut(String) + 'static>(&mut self, callback: F);
+
+    /// The current selection, as a `\n`-normalized UTF-8 byte range into `value()`.
+    fn selection(&self) -> Range<usize>;
+
+    /// Sets the selection to a `\n`-normalized UTF-8 byte range into `value()`.
+    fn set_selection(&mut self, range: Range<usize>);
+
+    /// The position of the caret, i.e. the end of the current selection.
+    fn caret(&self) -> usize {
+        self.selection().end
+    }
+
+    /// Selects the entire contents of the buffer.
+    fn select_all(&mut self) {
+        let len = self.value().len();
+        self.set_selection(0..len);
+    }
+
+    /// Replaces the current selection with `text` and places the caret after it.
+    fn replace_selection(&mut self, text: &str) {
+        let range = self.selection();
+        let mut value = self.value();
+        value.replace_range(range.start..range.end, text);
+        self.set_value(&value);
+        let caret = range.start + text.len();
+        self.set_selection(caret..caret);
+    }
+
+    /// Like `on_changed`, but runs `validator` on each change first. If it returns
+    /// `Err`, the buffer is reverted to the last accepted value (preserving the
+    /// caret position as closely as possible) instead of accepting the edit.
+    ///
+    /// Returns a handle holding the most recent rejection message (`None` once
+    /// the buffer holds an accepted value again), so input masks and
+    /// numeric/length constraints don't need to hand-roll revert logic, and
+    /// the message isn't simply dropped on the floor.
+    fn on_changed_validated<F>(&mut self, mut validator: F) -> Rc<RefCell<Option<String>>>
+    where
+        F: FnMut(&str) -> Result<(), String> + 'static,
+        Self: Clone + 'static,
+    {
+        let accepted = RefCell::new(self.value());
+        let last_rejection = Rc::new(RefCell::new(None));
+        let last_rejection_handle = last_rejection.clone();
+        // Guards against backends (e.g. GTK) whose `set_value` re-enters `on_changed`
+        // synchronously; without it the revert below would recurse into itself.
+        let reverting = Cell::new(false);
+        let mut entry = self.clone();
+        self.on_changed(move |new_value| {
+            if reverting.get() {
+                return;
+            }
+            match validator(&new_value) {
+                Ok(()) => {
+                    *accepted.borrow_mut() = new_value;
+                    *last_rejection.borrow_mut() = None;
+                }
+                Err(message) => {
+                    let last_accepted = accepted.borrow().clone();
+                    let caret = entry.caret().min(last_accepted.len());
+                    reverting.set(true);
+                    entry.set_value(&last_accepted);
+                    entry.set_selection(caret..caret);
+                    reverting.set(false);
+                    *last_rejection.borrow_mut() = Some(message);
+                }
+            }
+        });
+        last_rejection_handle
+    }
 }
 
 define_control! {
@@ -107,6 +237,17 @@ impl TextEntry for Entry {
             ui_sys::uiEntryOnChanged(self.uiEntry, Some(c_callback::<F>), to_heap_ptr(callback));
         }
     }
+
+    fn selection(&self) -> Range<usize> {
+        unsafe {
+            selection_from_native(ui_sys::uiEntryText(self.uiEntry), self.uiEntry, ui_sys::uiEntrySelectionBounds)
+        }
+    }
+
+    fn set_selection(&mut self, range: Range<usize>) {
+        let value = self.value();
+        unsafe { set_selection_on(&value, self.uiEntry, range, ui_sys::uiEntrySetSelection) }
+    }
 }
 
 impl TextEntry for PasswordEntry {
@@ -142,6 +283,17 @@ impl TextEntry for PasswordEntry {
             }
         }
     }
+
+    fn selection(&self) -> Range<usize> {
+        unsafe {
+            selection_from_native(ui_sys::uiEntryText(self.uiEntry), self.uiEntry, ui_sys::uiEntrySelectionBounds)
+        }
+    }
+
+    fn set_selection(&mut self, range: Range<usize>) {
+        let value = self.value();
+        unsafe { set_selection_on(&value, self.uiEntry, range, ui_sys::uiEntrySetSelection) }
+    }
 }
 
 impl TextEntry for SearchEntry {
@@ -173,6 +325,17 @@ impl TextEntry for SearchEntry {
             ui_sys::uiEntryOnChanged(self.uiEntry, Some(c_callback::<F>), to_heap_ptr(callback));
         }
     }
+
+    fn selection(&self) -> Range<usize> {
+        unsafe {
+            selection_from_native(ui_sys::uiEntryText(self.uiEntry), self.uiEntry, ui_sys::uiEntrySelectionBounds)
+        }
+    }
+
+    fn set_selection(&mut self, range: Range<usize>) {
+        let value = self.value();
+        unsafe { set_selection_on(&value, self.uiEntry, range, ui_sys::uiEntrySetSelection) }
+    }
 }
 
 impl TextEntry for MultilineEntry {
@@ -207,4 +370,19 @@ impl TextEntry for MultilineEntry {
             );
         }
     }
+
+    fn selection(&self) -> Range<usize> {
+        unsafe {
+            selection_from_native(
+                ui_sys::uiMultilineEntryText(self.uiMultilineEntry),
+                self.uiMultilineEntry,
+                ui_sys::uiMultilineEntrySelectionBounds,
+            )
+        }
+    }
+
+    fn set_selection(&mut self, range: Range<usize>) {
+        let value = self.value();
+        unsafe { set_selection_on(&value, self.uiMultilineEntry, range, ui_sys::uiMultilineEntrySetSelection) }
+    }
 }
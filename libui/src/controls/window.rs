@@ -7,6 +7,7 @@ use std::ffi::{CStr, CString};
 use std::mem;
 use std::os::raw::{c_int, c_void};
 use std::path::PathBuf;
+use std::ptr;
 use ui::UI;
 use libui_ffi::{self, uiControl, uiFreeText, uiWindow};
 
@@ -21,6 +22,140 @@ pub enum WindowType {
     NoMenubar,
 }
 
+/// A named group of file extensions shown in a file dialog's type selector,
+/// e.g. `("Images", &["png", "jpg"])`.
+#[derive(Clone, Debug)]
+pub struct FileTypeFilter {
+    name: String,
+    extensions: Vec<String>,
+}
+
+impl FileTypeFilter {
+    /// Create a filter with the given display name and extensions (without the leading dot).
+    pub fn new<S: Into<String>>(name: S, extensions: &[&str]) -> FileTypeFilter {
+        FileTypeFilter {
+            name: name.into(),
+            extensions: extensions.iter().map(|&s| s.to_string()).collect(),
+        }
+    }
+}
+
+/// Options controlling the appearance and behavior of the system file dialogs
+/// opened via `Window::open_file_with`, `Window::open_files_with`,
+/// `Window::save_file_with`, and `Window::open_folder_with`.
+///
+/// Any option a given backend can't honor (filters, a starting directory, a
+/// suggested filename, or a title override) is silently ignored rather than
+/// causing an error. In particular, without the `file-dialog-params` feature
+/// every option is ignored and the bare system dialog is shown instead.
+#[derive(Clone, Debug, Default)]
+pub struct FileDialogOptions {
+    filters: Vec<FileTypeFilter>,
+    show_all_files_filter: bool,
+    default_path: Option<PathBuf>,
+    default_name: Option<String>,
+    title: Option<String>,
+    allow_multiple: bool,
+}
+
+impl FileDialogOptions {
+    /// Create an empty set of options; every option defaults to "don't care".
+    pub fn new() -> FileDialogOptions {
+        FileDialogOptions::default()
+    }
+
+    /// Add a named extension filter, e.g. `("Images", &["png", "jpg"])`.
+    pub fn add_filter<S: Into<String>>(mut self, name: S, extensions: &[&str]) -> FileDialogOptions {
+        self.filters.push(FileTypeFilter::new(name, extensions));
+        self
+    }
+
+    /// Add a catch-all "All Files" entry alongside any named filters.
+    pub fn show_all_files_filter(mut self, show: bool) -> FileDialogOptions {
+        self.show_all_files_filter = show;
+        self
+    }
+
+    /// Set the directory the dialog should start in.
+    pub fn default_path<P: Into<PathBuf>>(mut self, path: P) -> FileDialogOptions {
+        self.default_path = Some(path.into());
+        self
+    }
+
+    /// Set the suggested filename for save dialogs.
+    pub fn default_name<S: Into<String>>(mut self, name: S) -> FileDialogOptions {
+        self.default_name = Some(name.into());
+        self
+    }
+
+    /// Override the dialog window's title.
+    pub fn title<S: Into<String>>(mut self, title: S) -> FileDialogOptions {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Allow selecting more than one file. Only honored by `Window::open_files_with`;
+    /// ignored by the single-file, save, and folder dialogs.
+    pub fn allow_multiple(mut self, allow: bool) -> FileDialogOptions {
+        self.allow_multiple = allow;
+        self
+    }
+
+    // Builds the FFI params struct and hands it to `f` while the backing CStrings
+    // are still alive, since `uiFileDialogParams` only borrows pointers into them.
+    //
+    // Only available when the `file-dialog-params` feature is enabled, since stock
+    // libui-ng only exposes the bare, argument-less dialog functions; builds against
+    // an ffi without this surface fall back to those instead (see `Window::open_file_with`
+    // and friends below).
+    #[cfg(feature = "file-dialog-params")]
+    fn with_raw_params<R>(&self, f: impl FnOnce(&libui_ffi::uiFileDialogParams) -> R) -> R {
+        let filter_strings: Vec<(CString, CString)> = self
+            .filters
+            .iter()
+            .map(|filter| {
+                let name = CString::new(filter.name.clone()).unwrap();
+                let extensions = CString::new(filter.extensions.join(";")).unwrap();
+                (name, extensions)
+            })
+            .collect();
+        let c_filters: Vec<libui_ffi::uiFileTypeFilter> = filter_strings
+            .iter()
+            .map(|(name, extensions)| libui_ffi::uiFileTypeFilter {
+                name: name.as_ptr(),
+                extensions: extensions.as_ptr(),
+            })
+            .collect();
+
+        let c_default_path = self
+            .default_path
+            .as_ref()
+            .map(|p| CString::new(p.to_string_lossy().into_owned()).unwrap());
+        let c_default_name = self.default_name.as_ref().map(|n| CString::new(n.as_str()).unwrap());
+        let c_title = self.title.as_ref().map(|t| CString::new(t.as_str()).unwrap());
+
+        f(&libui_ffi::uiFileDialogParams {
+            filters: c_filters.as_ptr(),
+            filterCount: c_filters.len(),
+            showAllFilesFilter: self.show_all_files_filter as c_int,
+            defaultPath: c_default_path.as_ref().map_or(ptr::null(), |s| s.as_ptr()),
+            defaultName: c_default_name.as_ref().map_or(ptr::null(), |s| s.as_ptr()),
+            title: c_title.as_ref().map_or(ptr::null(), |s| s.as_ptr()),
+            allowMultiple: self.allow_multiple as c_int,
+        })
+    }
+}
+
+// Converts a raw, libui-owned C string into an owned `PathBuf`, freeing the original.
+unsafe fn path_from_raw(ptr: *mut ::std::os::raw::c_char) -> Option<PathBuf> {
+    if ptr.is_null() {
+        return None;
+    }
+    let path_string: String = CStr::from_ptr(ptr).to_string_lossy().into();
+    uiFreeText(ptr);
+    Some(path_string.into())
+}
+
 define_control! {
     /// Contains a single child control and displays it and its children in a window on the screen.
     rust_type: Window,
@@ -151,6 +286,66 @@ impl Window {
         }
     }
 
+    /// Gets the size of the window's content area (i.e. excluding the titlebar and borders).
+    pub fn content_size(&self) -> (i32, i32) {
+        let mut width: c_int = 0;
+        let mut height: c_int = 0;
+        unsafe { libui_ffi::uiWindowContentSize(self.uiWindow, &mut width, &mut height) }
+
+        (width.into(), height.into())
+    }
+
+    /// Resizes the window's content area (i.e. excluding the titlebar and borders).
+    ///
+    /// This method is merely a hint and may be ignored on some platforms.
+    pub fn set_content_size(&mut self, width: i32, height: i32) {
+        unsafe { libui_ffi::uiWindowSetContentSize(self.uiWindow, width, height) }
+    }
+
+    /// Sets a callback to be run when the user resizes the window.
+    ///
+    /// Note that this callback does not trigger when the window is resized through the
+    /// `set_content_size` method. It triggers when the user drags an edge of the window,
+    /// not when the application changes its own size.
+    pub fn on_content_size_changed<'ctx, F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut Window) + 'static,
+    {
+        extern "C" fn c_callback<G>(window: *mut uiWindow, data: *mut c_void)
+        where
+            G: FnMut(&mut Window),
+        {
+            let mut window = Window { uiWindow: window };
+            unsafe {
+                from_void_ptr::<G>(data)(&mut window);
+            }
+        }
+
+        unsafe {
+            libui_ffi::uiWindowOnContentSizeChanged(self.uiWindow, Some(c_callback::<F>), to_heap_ptr(callback));
+        }
+    }
+
+    /// Check whether or not this window is currently fullscreen.
+    pub fn fullscreen(&self) -> bool {
+        unsafe { libui_ffi::uiWindowFullscreen(self.uiWindow) != 0 }
+    }
+
+    /// Set whether or not this window should be fullscreen.
+    pub fn set_fullscreen(&mut self, fullscreen: bool) {
+        unsafe { libui_ffi::uiWindowSetFullscreen(self.uiWindow, fullscreen as c_int) }
+    }
+
+    /// Check whether or not this window is currently drawn without a titlebar or borders.
+    pub fn borderless(&self) -> bool {
+        unsafe { libui_ffi::uiWindowBorderless(self.uiWindow) != 0 }
+    }
+
+    /// Set whether or not this window should be drawn without a titlebar or borders.
+    pub fn set_borderless(&mut self, borderless: bool) {
+        unsafe { libui_ffi::uiWindowSetBorderless(self.uiWindow, borderless as c_int) }
+    }
+
     /// Check whether or not this window has margins around the edges.
     pub fn margined(&self) -> bool {
         unsafe { libui_ffi::uiWindowMargined(self.uiWindow) != 0 }
@@ -217,6 +412,96 @@ impl Window {
         Some(path_string.into())
     }
 
+    /// Allow the user to select an existing file using the systems file dialog,
+    /// with control over filters, starting directory, and title.
+    ///
+    /// Requires the `file-dialog-params` feature; without it, falls back to the
+    /// bare `open_file` dialog and silently ignores `opts`.
+    #[cfg(feature = "file-dialog-params")]
+    pub fn open_file_with(&self, opts: &FileDialogOptions) -> Option<PathBuf> {
+        let ptr = opts.with_raw_params(|params| unsafe {
+            libui_ffi::uiOpenFileWithParams(self.uiWindow, params)
+        });
+        unsafe { path_from_raw(ptr) }
+    }
+
+    #[cfg(not(feature = "file-dialog-params"))]
+    pub fn open_file_with(&self, _opts: &FileDialogOptions) -> Option<PathBuf> {
+        self.open_file()
+    }
+
+    /// Allow the user to select one or more existing files using the systems file dialog.
+    ///
+    /// Honors `opts.allow_multiple`; set it to select more than one file.
+    ///
+    /// Requires the `file-dialog-params` feature; without it, falls back to the
+    /// bare `open_file` dialog (so at most one path) and silently ignores `opts`.
+    #[cfg(feature = "file-dialog-params")]
+    pub fn open_files_with(&self, opts: &FileDialogOptions) -> Vec<PathBuf> {
+        let mut count: usize = 0;
+        let paths = opts.with_raw_params(|params| unsafe {
+            libui_ffi::uiOpenFilesWithParams(self.uiWindow, params, &mut count)
+        });
+        if paths.is_null() {
+            return Vec::new();
+        }
+        // `uiFreeFilenames` owns and frees both the array and its element strings,
+        // so read each path without freeing it individually first.
+        let result = (0..count)
+            .filter_map(|i| unsafe {
+                let ptr = *paths.add(i);
+                if ptr.is_null() {
+                    return None;
+                }
+                let path_string: String = CStr::from_ptr(ptr).to_string_lossy().into();
+                Some(path_string.into())
+            })
+            .collect();
+        unsafe { libui_ffi::uiFreeFilenames(paths, count) };
+        result
+    }
+
+    #[cfg(not(feature = "file-dialog-params"))]
+    pub fn open_files_with(&self, _opts: &FileDialogOptions) -> Vec<PathBuf> {
+        self.open_file().into_iter().collect()
+    }
+
+    /// Allow the user to select a new or existing file using the systems file dialog,
+    /// with control over filters, starting directory, default filename, and title.
+    ///
+    /// Requires the `file-dialog-params` feature; without it, falls back to the
+    /// bare `save_file` dialog and silently ignores `opts`.
+    #[cfg(feature = "file-dialog-params")]
+    pub fn save_file_with(&self, opts: &FileDialogOptions) -> Option<PathBuf> {
+        let ptr = opts.with_raw_params(|params| unsafe {
+            libui_ffi::uiSaveFileWithParams(self.uiWindow, params)
+        });
+        unsafe { path_from_raw(ptr) }
+    }
+
+    #[cfg(not(feature = "file-dialog-params"))]
+    pub fn save_file_with(&self, _opts: &FileDialogOptions) -> Option<PathBuf> {
+        self.save_file()
+    }
+
+    /// Allow the user to select a single folder using the systems folder dialog,
+    /// with control over the starting directory and title.
+    ///
+    /// Requires the `file-dialog-params` feature; without it, falls back to the
+    /// bare `open_folder` dialog and silently ignores `opts`.
+    #[cfg(feature = "file-dialog-params")]
+    pub fn open_folder_with(&self, opts: &FileDialogOptions) -> Option<PathBuf> {
+        let ptr = opts.with_raw_params(|params| unsafe {
+            libui_ffi::uiOpenFolderWithParams(self.uiWindow, params)
+        });
+        unsafe { path_from_raw(ptr) }
+    }
+
+    #[cfg(not(feature = "file-dialog-params"))]
+    pub fn open_folder_with(&self, _opts: &FileDialogOptions) -> Option<PathBuf> {
+        self.open_folder()
+    }
+
     /// Open a generic message box to show a message to the user.
     /// Returns when the user acknowledges the message.
     pub fn modal_msg(&self, title: &str, description: &str) {
@@ -252,4 +537,188 @@ impl Window {
         // Don't check for initialization here since this can be run during deinitialization.
         libui_ffi::uiControlDestroy(self.uiWindow as *mut libui_ffi::uiControl)
     }
+
+    /// Returns the monitor this window is mostly contained within.
+    pub fn current_monitor(&self) -> Monitor {
+        unsafe { Monitor::from_raw(libui_ffi::uiWindowMonitor(self.uiWindow)) }
+    }
+
+    /// The content scale factor (DPI ratio) of the monitor this window is currently on,
+    /// e.g. `1.0`, `1.5`, or `2.0`. Useful for sizing fonts and custom-drawn controls
+    /// correctly on HiDPI screens.
+    pub fn content_scale(&self) -> f64 {
+        unsafe { libui_ffi::uiWindowContentScale(self.uiWindow) }
+    }
+}
+
+/// A rectangle in screen coordinates, used to describe monitor bounds.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rect {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+/// A physical display attached to the system.
+///
+/// The underlying `uiMonitor` handle is only valid while it's being read, so
+/// `Monitor` snapshots its data up front rather than holding onto the handle:
+/// `uiFreeMonitorsList` isn't documented to leave the individual monitor
+/// objects alive afterwards, and re-querying through a freed handle would be
+/// a use-after-free.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Monitor {
+    name: String,
+    bounds: Rect,
+    work_area: Rect,
+    content_scale: f64,
+}
+
+impl Monitor {
+    /// Enumerate every monitor currently attached to the system.
+    pub fn all() -> Vec<Monitor> {
+        unsafe {
+            let mut count: usize = 0;
+            let handles = libui_ffi::uiMonitorsList(&mut count);
+            let monitors = (0..count).map(|i| Monitor::from_raw(*handles.add(i))).collect();
+            libui_ffi::uiFreeMonitorsList(handles, count);
+            monitors
+        }
+    }
+
+    /// The monitor the system considers primary (usually the one holding the menu bar/taskbar).
+    pub fn primary() -> Monitor {
+        unsafe { Monitor::from_raw(libui_ffi::uiMonitorPrimary()) }
+    }
+
+    // Reads everything out of a `uiMonitor` handle immediately, before it can be freed.
+    unsafe fn from_raw(handle: *mut libui_ffi::uiMonitor) -> Monitor {
+        let name_ptr = libui_ffi::uiMonitorName(handle);
+        let name = CStr::from_ptr(name_ptr).to_string_lossy().into_owned();
+        uiFreeText(name_ptr);
+
+        let mut bounds = Rect { x: 0, y: 0, width: 0, height: 0 };
+        libui_ffi::uiMonitorBounds(
+            handle,
+            &mut bounds.x,
+            &mut bounds.y,
+            &mut bounds.width,
+            &mut bounds.height,
+        );
+
+        let mut work_area = Rect { x: 0, y: 0, width: 0, height: 0 };
+        libui_ffi::uiMonitorWorkArea(
+            handle,
+            &mut work_area.x,
+            &mut work_area.y,
+            &mut work_area.width,
+            &mut work_area.height,
+        );
+
+        let content_scale = libui_ffi::uiMonitorContentScale(handle);
+
+        Monitor {
+            name,
+            bounds,
+            work_area,
+            content_scale,
+        }
+    }
+
+    /// A human-readable name for the monitor, e.g. "Built-in Retina Display".
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The monitor's full bounds in screen coordinates.
+    pub fn bounds(&self) -> Rect {
+        self.bounds
+    }
+
+    /// The monitor's usable area, excluding docks, taskbars, and menu bars.
+    pub fn work_area(&self) -> Rect {
+        self.work_area
+    }
+
+    /// The monitor's content scale factor (DPI ratio), e.g. `1.0`, `1.5`, or `2.0`.
+    pub fn content_scale(&self) -> f64 {
+        self.content_scale
+    }
+}
+
+// Lets a `Window` host an external renderer (wgpu, glow, skia, ...) in its
+// client area by exposing the native handle of the underlying toolkit window.
+#[cfg(feature = "raw-window-handle")]
+mod raw_window_handle_impl {
+    use super::Window;
+    use raw_window_handle::{
+        HasRawDisplayHandle, HasRawWindowHandle, RawDisplayHandle, RawWindowHandle,
+    };
+
+    #[cfg(target_os = "windows")]
+    unsafe impl HasRawWindowHandle for Window {
+        fn raw_window_handle(&self) -> RawWindowHandle {
+            let mut handle = raw_window_handle::Win32WindowHandle::empty();
+            handle.hwnd = unsafe { libui_ffi::uiWindowsWindowHWND(self.uiWindow) };
+            RawWindowHandle::Win32(handle)
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    unsafe impl HasRawWindowHandle for Window {
+        fn raw_window_handle(&self) -> RawWindowHandle {
+            let mut handle = raw_window_handle::AppKitWindowHandle::empty();
+            handle.ns_view = unsafe { libui_ffi::uiDarwinWindowNSView(self.uiWindow) };
+            RawWindowHandle::AppKit(handle)
+        }
+    }
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    unsafe impl HasRawWindowHandle for Window {
+        fn raw_window_handle(&self) -> RawWindowHandle {
+            if unsafe { libui_ffi::uiUnixWindowIsWayland(self.uiWindow) } != 0 {
+                let mut handle = raw_window_handle::WaylandWindowHandle::empty();
+                handle.surface = unsafe { libui_ffi::uiUnixWindowWaylandSurface(self.uiWindow) };
+                RawWindowHandle::Wayland(handle)
+            } else {
+                let mut handle = raw_window_handle::XlibWindowHandle::empty();
+                handle.window = unsafe { libui_ffi::uiUnixWindowXWindow(self.uiWindow) };
+                RawWindowHandle::Xlib(handle)
+            }
+        }
+    }
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    unsafe impl HasRawDisplayHandle for Window {
+        fn raw_display_handle(&self) -> RawDisplayHandle {
+            if unsafe { libui_ffi::uiUnixWindowIsWayland(self.uiWindow) } != 0 {
+                let mut handle = raw_window_handle::WaylandDisplayHandle::empty();
+                handle.display = unsafe { libui_ffi::uiUnixWindowWaylandDisplay(self.uiWindow) };
+                RawDisplayHandle::Wayland(handle)
+            } else {
+                let mut handle = raw_window_handle::XlibDisplayHandle::empty();
+                handle.display = unsafe { libui_ffi::uiUnixWindowXDisplay(self.uiWindow) };
+                RawDisplayHandle::Xlib(handle)
+            }
+        }
+    }
+
+    // Windows and macOS don't have a distinct display handle; both platforms
+    // route this through a zero-sized marker handle. Split into two impls
+    // (rather than one body with cfg'd `return`s) so each compiles down to a
+    // single trailing expression.
+    #[cfg(target_os = "windows")]
+    unsafe impl HasRawDisplayHandle for Window {
+        fn raw_display_handle(&self) -> RawDisplayHandle {
+            RawDisplayHandle::Windows(raw_window_handle::WindowsDisplayHandle::empty())
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    unsafe impl HasRawDisplayHandle for Window {
+        fn raw_display_handle(&self) -> RawDisplayHandle {
+            RawDisplayHandle::AppKit(raw_window_handle::AppKitDisplayHandle::empty())
+        }
+    }
 }